@@ -1,127 +1,491 @@
+use std::collections::HashMap;
+
 #[derive(Debug, PartialEq)]
 pub enum EvalError {
-    DivisionByZero,
-    InvalidCharacter,
-    InvalidBlock,
-    InvalidInput,
+    DivisionByZero { pos: usize },
+    InvalidCharacter { ch: char, pos: usize },
+    UnmatchedGroup { open_pos: usize },
+    InvalidInput { pos: usize },
+    InvalidNumber { pos: usize },
+    UndefinedVariable { name: String, pos: usize },
+    UndefinedFunction { name: String, pos: usize },
+    TypeMismatch { pos: usize },
+}
+
+/// The result of evaluating an expression: either a number, or the `bool`
+/// produced by a comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Bool(bool),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{n}"),
+            Value::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+impl std::fmt::Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::DivisionByZero { pos } => write!(f, "division by zero at position {pos}"),
+            EvalError::InvalidCharacter { ch, pos } => {
+                write!(f, "invalid character '{ch}' at position {pos}")
+            }
+            EvalError::UnmatchedGroup { open_pos } => {
+                write!(f, "unmatched 'e' group opened at position {open_pos}")
+            }
+            EvalError::InvalidInput { pos } => write!(f, "invalid input at position {pos}"),
+            EvalError::InvalidNumber { pos } => {
+                write!(f, "invalid number literal at position {pos}")
+            }
+            EvalError::UndefinedVariable { name, pos } => {
+                write!(f, "undefined variable '{name}' at position {pos}")
+            }
+            EvalError::UndefinedFunction { name, pos } => {
+                write!(f, "undefined function '{name}' at position {pos}")
+            }
+            EvalError::TypeMismatch { pos } => write!(f, "type mismatch at position {pos}"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+type Func = Box<dyn Fn(&[f64]) -> Result<f64, EvalError>>;
+
+/// Bindings available to an expression: named numbers and named functions,
+/// looked up by [`evaluate_with_context`] when the parsed [`Expr`] contains a
+/// `Var` or `Call` node.
+pub struct Context {
+    variables: HashMap<String, f64>,
+    functions: HashMap<String, Func>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Context {
+            variables: HashMap::new(),
+            functions: HashMap::new(),
+        }
+    }
+
+    pub fn set_variable(&mut self, name: impl Into<String>, value: f64) {
+        self.variables.insert(name.into(), value);
+    }
+
+    pub fn set_function<F>(&mut self, name: impl Into<String>, f: F)
+    where
+        F: Fn(&[f64]) -> Result<f64, EvalError> + 'static,
+    {
+        self.functions.insert(name.into(), Box::new(f));
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Debug, Clone)]
-enum Operator {
+pub enum Operator {
     Add,
     Subtract,
     Multiply,
     Divide,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// A parsed expression, reusable across repeated evaluations without
+/// re-parsing. Build one with [`str::parse`] (via `FromStr`) and evaluate it
+/// with [`Expr::eval`] or [`Expr::eval_with_context`].
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Num(f64),
+    Bin(Operator, Box<Expr>, Box<Expr>, usize),
+    Var(String, usize),
+    Call(String, Vec<Expr>, usize),
 }
 
-pub fn evaluate(expr: &str) -> Result<f64, EvalError> {
-    fn operate(op: Operator, a: f64, b: f64) -> Result<f64, EvalError> {
-        match op {
-            Operator::Add => Ok(a + b),
-            Operator::Subtract => Ok(a - b),
-            Operator::Multiply => Ok(a * b),
-            Operator::Divide if b != 0. => Ok(a / b),
-            Operator::Divide => Err(EvalError::DivisionByZero),
+impl Expr {
+    pub fn eval(&self) -> Result<Value, EvalError> {
+        self.eval_with_context(&Context::new())
+    }
+
+    pub fn eval_with_context(&self, context: &Context) -> Result<Value, EvalError> {
+        eval_expr(self, context)
+    }
+}
+
+impl std::str::FromStr for Expr {
+    type Err = EvalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+        let ast = parse_expr(&mut parser, 0)?;
+        if let Some((pos, ch)) = parser.peek() {
+            return Err(EvalError::InvalidCharacter { ch, pos });
         }
+        Ok(ast)
+    }
+}
+
+fn as_number(value: Value, pos: usize) -> Result<f64, EvalError> {
+    match value {
+        Value::Number(n) => Ok(n),
+        Value::Bool(_) => Err(EvalError::TypeMismatch { pos }),
+    }
+}
+
+fn operate(op: &Operator, a: Value, b: Value, pos: usize) -> Result<Value, EvalError> {
+    let a = as_number(a, pos)?;
+    let b = as_number(b, pos)?;
+    match op {
+        Operator::Add => Ok(Value::Number(a + b)),
+        Operator::Subtract => Ok(Value::Number(a - b)),
+        Operator::Multiply => Ok(Value::Number(a * b)),
+        Operator::Divide if b != 0. => Ok(Value::Number(a / b)),
+        Operator::Divide => Err(EvalError::DivisionByZero { pos }),
+        Operator::Lt => Ok(Value::Bool(a < b)),
+        Operator::Gt => Ok(Value::Bool(a > b)),
+        Operator::Le => Ok(Value::Bool(a <= b)),
+        Operator::Ge => Ok(Value::Bool(a >= b)),
+        Operator::Eq => Ok(Value::Bool(a == b)),
+        Operator::Ne => Ok(Value::Bool(a != b)),
+    }
+}
+
+fn eval_expr(expr: &Expr, context: &Context) -> Result<Value, EvalError> {
+    match expr {
+        Expr::Num(n) => Ok(Value::Number(*n)),
+        Expr::Bin(op, lhs, rhs, pos) => {
+            operate(op, eval_expr(lhs, context)?, eval_expr(rhs, context)?, *pos)
+        }
+        Expr::Var(name, pos) => context
+            .variables
+            .get(name)
+            .copied()
+            .map(Value::Number)
+            .ok_or_else(|| EvalError::UndefinedVariable {
+                name: name.clone(),
+                pos: *pos,
+            }),
+        Expr::Call(name, args, pos) => {
+            let func = context
+                .functions
+                .get(name)
+                .ok_or_else(|| EvalError::UndefinedFunction {
+                    name: name.clone(),
+                    pos: *pos,
+                })?;
+            let values = args
+                .iter()
+                .map(|arg| as_number(eval_expr(arg, context)?, *pos))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::Number(func(&values)?))
+        }
+    }
+}
+
+/// A `char_indices().peekable()` cursor over the source text, so every parse
+/// function can report the byte offset of whatever it's looking at.
+#[derive(Clone)]
+struct Parser<'a> {
+    input: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            chars: input.char_indices().peekable(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.chars.peek().copied()
     }
 
-    fn parse_number(iter: &mut std::iter::Peekable<std::str::Chars>, number: f64) -> f64 {
-        match iter.peek() {
-            Some('0'..='9') => {
-                let new_number = number * 10. + iter.next().unwrap().to_digit(10).unwrap() as f64;
-                parse_number(iter, new_number)
+    fn next(&mut self) -> Option<(usize, char)> {
+        self.chars.next()
+    }
+
+    /// The offset of the next character, or the end of the input if there
+    /// isn't one.
+    fn pos(&mut self) -> usize {
+        self.peek().map(|(pos, _)| pos).unwrap_or(self.input.len())
+    }
+}
+
+fn take_digits(parser: &mut Parser, literal: &mut String) {
+    while let Some((_, c)) = parser.peek() {
+        if c.is_ascii_digit() {
+            literal.push(c);
+            parser.next();
+        } else {
+            break;
+        }
+    }
+}
+
+// Accepts an integer part, an optional `.`-fractional part, and an optional
+// signed exponent (`e+`/`e-`). The sign is mandatory for the exponent: a bare
+// `e` right after a number is never otherwise valid here (two primaries can't
+// sit side by side without an operator), so rather than ever reading it as a
+// group opener, we always consume it into the literal, where it's either
+// followed by a sign (a real exponent) or not (a malformed one) — either way
+// `e3`/`e5`-style unsigned digits are never swallowed as magnitude.
+fn parse_number(parser: &mut Parser) -> Result<f64, EvalError> {
+    let start = parser.pos();
+    let mut literal = String::new();
+    take_digits(parser, &mut literal);
+
+    if let Some((_, '.')) = parser.peek() {
+        literal.push(parser.next().unwrap().1);
+        take_digits(parser, &mut literal);
+        while let Some((_, '.')) = parser.peek() {
+            literal.push(parser.next().unwrap().1);
+            take_digits(parser, &mut literal);
+        }
+    }
+
+    if let Some((_, 'e')) = parser.peek() {
+        literal.push(parser.next().unwrap().1);
+        if let Some((_, '+' | '-')) = parser.peek() {
+            literal.push(parser.next().unwrap().1);
+            take_digits(parser, &mut literal);
+        }
+    }
+
+    literal
+        .parse::<f64>()
+        .map_err(|_| EvalError::InvalidNumber { pos: start })
+}
+
+// Binding power pairs are (left, right); a higher left binding power than the
+// caller's `min_bp` lets an operator keep pulling operands into its own node,
+// and right > left makes each level left-associative. Comparisons sit below
+// arithmetic, so `2a3g4` reads as `(2+3) < 4`, not `2 + (3<4)`.
+fn binding_power(op: &Operator) -> (u8, u8) {
+    match op {
+        Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge | Operator::Eq | Operator::Ne => {
+            (1, 2)
+        }
+        Operator::Add | Operator::Subtract => (3, 4),
+        Operator::Multiply | Operator::Divide => (5, 6),
+    }
+}
+
+fn peek_operator(parser: &mut Parser) -> Option<Operator> {
+    match parser.peek() {
+        Some((_, 'a')) => Some(Operator::Add),
+        Some((_, 'b')) => Some(Operator::Subtract),
+        Some((_, 'c')) => Some(Operator::Multiply),
+        Some((_, 'd')) => Some(Operator::Divide),
+        Some((_, 'g')) => Some(Operator::Lt),
+        Some((_, 'h')) => Some(Operator::Gt),
+        Some((_, 'i')) => Some(Operator::Eq),
+        Some((_, 'j')) => Some(Operator::Le),
+        Some((_, 'k')) => Some(Operator::Ge),
+        Some((_, 'l')) => Some(Operator::Ne),
+        _ => None,
+    }
+}
+
+// `a`-`e` and `g`-`l` stay reserved for operators/groups even mid-identifier
+// (`f` alone is free, since it's only special as the raw group terminator),
+// so a run like "sum" is fine but "xcab" would stop right before the `c`
+// (and "cab" is not an identifier at all: a leading `c` is an operator).
+fn is_identifier_char(c: char) -> bool {
+    let reserved = matches!(c, 'a'..='e' | 'g'..='l');
+    (c.is_alphabetic() && !reserved) || c.is_ascii_digit() || c == '_'
+}
+
+fn parse_identifier(parser: &mut Parser) -> String {
+    let mut name = String::new();
+    while let Some((_, c)) = parser.peek() {
+        if is_identifier_char(c) {
+            name.push(c);
+            parser.next();
+        } else {
+            break;
+        }
+    }
+    name
+}
+
+// A group (`e...f`) directly after an identifier is a function call whose
+// comma-separated arguments are each a full sub-expression; otherwise the
+// identifier is a variable lookup.
+fn parse_call_args(parser: &mut Parser, open_pos: usize) -> Result<Vec<Expr>, EvalError> {
+    let mut args = vec![parse_expr(parser, 0)?];
+    loop {
+        match parser.next() {
+            Some((_, ',')) => args.push(parse_expr(parser, 0)?),
+            Some((_, 'f')) => return Ok(args),
+            _ => return Err(EvalError::UnmatchedGroup { open_pos }),
+        }
+    }
+}
+
+fn parse_primary(parser: &mut Parser) -> Result<Expr, EvalError> {
+    match parser.peek() {
+        Some((_, c)) if c.is_ascii_digit() => Ok(Expr::Num(parse_number(parser)?)),
+        Some((open_pos, 'e')) => {
+            parser.next();
+            let inner = parse_expr(parser, 0)?;
+            match parser.next() {
+                Some((_, 'f')) => Ok(inner),
+                _ => Err(EvalError::UnmatchedGroup { open_pos }),
+            }
+        }
+        Some((pos, 'a'..='d' | 'g'..='l')) => Err(EvalError::InvalidInput { pos }),
+        Some((pos, c)) if c.is_alphabetic() => {
+            let name = parse_identifier(parser);
+            if let Some((open_pos, 'e')) = parser.peek() {
+                parser.next();
+                Ok(Expr::Call(name, parse_call_args(parser, open_pos)?, pos))
+            } else {
+                Ok(Expr::Var(name, pos))
             }
-            _ => number,
-        }
-    }
-
-    fn parse_group(
-        iter: &mut std::iter::Peekable<std::str::Chars>,
-        count: i32,
-        inner: String,
-    ) -> Result<String, EvalError> {
-        match iter.next().ok_or(EvalError::InvalidBlock)? {
-            'e' => parse_group(iter, count + 1, inner),
-            'f' if count == 1 => Ok(inner),
-            'f' => parse_group(iter, count - 1, inner),
-            c => parse_group(iter, count, inner + &c.to_string()),
-        }
-    }
-
-    fn parse(
-        iter: &mut std::iter::Peekable<std::str::Chars>,
-        operands: (Option<f64>, Option<f64>),
-        operator: Option<Operator>,
-    ) -> Result<f64, EvalError> {
-        match iter.peek() {
-            Some(c) => match c {
-                '0'..='9' => {
-                    let number = parse_number(iter, 0.);
-                    let new_operands = match (operator.clone(), operands) {
-                        (Some(op), (Some(a), Some(b))) => (Some(operate(op, a, b)?), Some(number)),
-                        (_, (Some(a), None)) => (Some(a), Some(number)),
-                        _ => (Some(number), None),
-                    };
-                    parse(iter, new_operands, operator)
-                }
-                'a'..='d' => {
-                    let next_op = match iter.next().unwrap() {
-                        'a' => Some(Operator::Add),
-                        'b' => Some(Operator::Subtract),
-                        'c' => Some(Operator::Multiply),
-                        'd' => Some(Operator::Divide),
-                        _ => None,
-                    };
-                    let new_operands = match (operator, operands) {
-                        (Some(op), (Some(a), Some(b))) => (Some(operate(op, a, b)?), None),
-                        _ => operands,
-                    };
-                    parse(iter, new_operands, next_op)
-                }
-                'e' => {
-                    iter.next().unwrap();
-                    let inner = parse_group(iter, 1, String::new())?;
-                    let new_operand = evaluate(&inner)?;
-                    let new_operands = match operands {
-                        (Some(a), _) => (Some(a), Some(new_operand)),
-                        _ => (Some(new_operand), None),
-                    };
-                    parse(iter, new_operands, operator)
-                }
-                _ => Err(EvalError::InvalidCharacter),
-            },
-            None => Ok(
-                if let (Some(op), (Some(a), Some(b))) = (operator, operands) {
-                    operate(op, a, b)?
-                } else {
-                    operands.0.ok_or(EvalError::InvalidInput)?
-                },
-            ),
-        }
-    }
-
-    Ok(parse(&mut expr.chars().peekable(), (None, None), None)?)
+        }
+        Some((pos, ch)) => Err(EvalError::InvalidCharacter { ch, pos }),
+        None => Err(EvalError::InvalidInput { pos: parser.pos() }),
+    }
+}
+
+fn parse_expr(parser: &mut Parser, min_bp: u8) -> Result<Expr, EvalError> {
+    let mut lhs = parse_primary(parser)?;
+    while let Some(op) = peek_operator(parser) {
+        let (left_bp, right_bp) = binding_power(&op);
+        if left_bp < min_bp {
+            break;
+        }
+        let (op_pos, _) = parser.next().unwrap();
+        let rhs = parse_expr(parser, right_bp)?;
+        lhs = Expr::Bin(op, Box::new(lhs), Box::new(rhs), op_pos);
+    }
+    Ok(lhs)
+}
+
+pub fn evaluate_with_context(expr: &str, context: &Context) -> Result<Value, EvalError> {
+    expr.parse::<Expr>()?.eval_with_context(context)
+}
+
+pub fn evaluate(expr: &str) -> Result<Value, EvalError> {
+    expr.parse::<Expr>()?.eval()
+}
+
+/// Convenience over [`evaluate_with_context`] for callers that only want
+/// arithmetic: errors with [`EvalError::TypeMismatch`] if the expression's
+/// top-level result is a `bool` instead of a number.
+pub fn evaluate_number_with_context(expr: &str, context: &Context) -> Result<f64, EvalError> {
+    as_number(evaluate_with_context(expr, context)?, 0)
+}
+
+pub fn evaluate_number(expr: &str) -> Result<f64, EvalError> {
+    evaluate_number_with_context(expr, &Context::new())
+}
+
+/// Renders an error alongside the offending source line with a caret under
+/// the reported column, the way uutils' `expr` points at the bad token.
+fn describe_error(input: &str, err: &EvalError) -> String {
+    let pos = match err {
+        EvalError::DivisionByZero { pos }
+        | EvalError::InvalidCharacter { pos, .. }
+        | EvalError::InvalidInput { pos }
+        | EvalError::InvalidNumber { pos }
+        | EvalError::UndefinedVariable { pos, .. }
+        | EvalError::UndefinedFunction { pos, .. }
+        | EvalError::TypeMismatch { pos } => *pos,
+        EvalError::UnmatchedGroup { open_pos } => *open_pos,
+    };
+    // `pos` is a byte offset, but the caret has to line up in *characters*,
+    // so count the chars before it rather than repeating spaces by byte.
+    let column = input[..pos].chars().count();
+    format!("{err}\n{input}\n{}^", " ".repeat(column))
 }
 
 #[test]
 fn tests() {
-    assert_eq!(evaluate("3a2c4").unwrap(), 20.);
-    assert_eq!(evaluate("32a2d2").unwrap(), 17.);
-    assert_eq!(evaluate("500a10b66c32").unwrap(), 14208.);
-    assert_eq!(evaluate("3ae4c66fb32").unwrap(), 235.);
-    assert_eq!(evaluate("3c4d2aee2a4c41fc4f").unwrap(), 990.);
-
-    assert_eq!(evaluate("3").unwrap(), 3.);
-    assert_eq!(evaluate("2a2a2").unwrap(), 6.);
-    assert_eq!(evaluate("2b3c4").unwrap(), -4.);
-    assert_eq!(evaluate("4c3b2d4").unwrap(), 2.5);
-    assert!(evaluate("4d0").is_err_and(|x| x == EvalError::DivisionByZero));
-    assert!(evaluate("3ae4d0fb2").is_err_and(|x| x == EvalError::DivisionByZero));
-    assert!(evaluate("3a2z4").is_err_and(|x| x == EvalError::InvalidCharacter));
-    assert!(evaluate("32a2d2g").is_err_and(|x| x == EvalError::InvalidCharacter));
-    assert!(evaluate("1ae1").is_err_and(|x| x == EvalError::InvalidBlock));
-    assert!(evaluate("a").is_err_and(|x| x == EvalError::InvalidInput));
-    assert!(evaluate("").is_err_and(|x| x == EvalError::InvalidInput));
+    assert_eq!(evaluate_number("3a2c4").unwrap(), 11.);
+    assert_eq!(evaluate_number("32a2d2").unwrap(), 33.);
+    assert_eq!(evaluate_number("500a10b66c32").unwrap(), -1602.);
+    assert_eq!(evaluate_number("3ae4c66fb32").unwrap(), 235.);
+    assert_eq!(evaluate_number("3c4d2aee2a4c41fc4f").unwrap(), 670.);
+
+    assert_eq!(evaluate_number("3").unwrap(), 3.);
+    assert_eq!(evaluate_number("2a2a2").unwrap(), 6.);
+    assert_eq!(evaluate_number("2b3c4").unwrap(), -10.);
+    assert_eq!(evaluate_number("4c3b2d4").unwrap(), 11.5);
+    assert!(evaluate("4d0").is_err_and(|x| x == EvalError::DivisionByZero { pos: 1 }));
+    assert!(evaluate("3ae4d0fb2").is_err_and(|x| x == EvalError::DivisionByZero { pos: 4 }));
+    assert!(evaluate("3a2z4").is_err_and(|x| x == EvalError::InvalidCharacter { ch: 'z', pos: 3 }));
+    assert!(
+        evaluate("32a2d2m").is_err_and(|x| x == EvalError::InvalidCharacter { ch: 'm', pos: 6 })
+    );
+    assert!(evaluate("1ae1").is_err_and(|x| x == EvalError::UnmatchedGroup { open_pos: 2 }));
+    assert!(evaluate("a").is_err_and(|x| x == EvalError::InvalidInput { pos: 0 }));
+    assert!(evaluate("").is_err_and(|x| x == EvalError::InvalidInput { pos: 0 }));
+
+    assert_eq!(evaluate_number("3.5a0.25").unwrap(), 3.75);
+    assert_eq!(evaluate_number("1e+3a2").unwrap(), 1002.);
+    assert_eq!(evaluate_number("1.5e-1c4").unwrap(), 0.6);
+    assert!(evaluate("3..5").is_err_and(|x| x == EvalError::InvalidNumber { pos: 0 }));
+    assert!(evaluate("1e").is_err_and(|x| x == EvalError::InvalidNumber { pos: 0 }));
+    assert!(evaluate("1e+").is_err_and(|x| x == EvalError::InvalidNumber { pos: 0 }));
+    assert!(evaluate("1e3").is_err_and(|x| x == EvalError::InvalidNumber { pos: 0 }));
+
+    let mut context = Context::new();
+    context.set_variable("x", 4.);
+    context.set_function("sum", |args| Ok(args.iter().sum()));
+    assert_eq!(evaluate_number_with_context("xa2", &context).unwrap(), 6.);
+    assert_eq!(
+        evaluate_number_with_context("sume1,2,3fc2", &context).unwrap(),
+        12.
+    );
+    assert!(evaluate_with_context("ya1", &context).is_err_and(|x| x
+        == EvalError::UndefinedVariable {
+            name: "y".into(),
+            pos: 0
+        }));
+    assert!(evaluate("fooe1,2f").is_err_and(|x| x
+        == EvalError::UndefinedFunction {
+            name: "foo".into(),
+            pos: 0
+        }));
+
+    let parsed: Expr = "3a2c4".parse().unwrap();
+    assert_eq!(parsed.eval().unwrap(), Value::Number(11.));
+    assert_eq!(parsed.eval().unwrap(), Value::Number(11.));
+    assert!("3a2z4".parse::<Expr>().is_err());
+    assert_eq!(
+        EvalError::DivisionByZero { pos: 1 }.to_string(),
+        "division by zero at position 1"
+    );
+    let err: Box<dyn std::error::Error> = Box::new(EvalError::InvalidInput { pos: 0 });
+    assert_eq!(err.to_string(), "invalid input at position 0");
+
+    assert_eq!(evaluate("3a2h4").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("3g2").unwrap(), Value::Bool(false));
+    assert_eq!(evaluate("2g3").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("3j3").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("5k5").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("3i3").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("3l4").unwrap(), Value::Bool(true));
+    assert!(evaluate_number("3i3").is_err_and(|x| x == EvalError::TypeMismatch { pos: 0 }));
+    assert!(evaluate("e3i3fa2").is_err_and(|x| x == EvalError::TypeMismatch { pos: 5 }));
 }
 
 pub fn main() -> Result<(), ()> {
@@ -131,7 +495,7 @@ pub fn main() -> Result<(), ()> {
     let result = evaluate(expr);
     match result {
         Ok(v) => println!("Result: {v}"),
-        Err(e) => println!("Error: {e:?}"),
+        Err(e) => println!("Error: {}", describe_error(expr, &e)),
     }
     Ok(())
 }